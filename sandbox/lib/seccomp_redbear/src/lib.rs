@@ -14,39 +14,254 @@ mod nodejs_syscalls;
 #[cfg(feature = "nodejs")]
 use crate::nodejs_syscalls::*;
 
+mod profile;
+
 use libc::{c_char, c_int, chdir, chroot, gid_t, uid_t};
 use libseccomp_sys::*;
 use std::env;
 use std::ffi::CString;
 use std::str::FromStr;
 
+/*
+ * ArgRule - one seccomp_rule_add_array() call for a single syscall
+ * @syscall: the syscall number the rule applies to
+ * @args: argument comparators to AND together for this rule
+ *
+ * libseccomp ANDs every comparator within a single rule, but ORs
+ * multiple rules added for the same syscall. So a syscall that needs
+ * to be allowed under several independent argument combinations (e.g.
+ * `socket` for each address family we support) is represented as
+ * several `ArgRule`s sharing the same `syscall`, each with its own
+ * (possibly empty) `args`. An empty `args` list means "match any
+ * arguments", i.e. the original unfiltered behavior.
+ */
+#[derive(Clone)]
+pub struct ArgRule {
+    pub syscall: i32,
+    pub args: Vec<scmp_arg_cmp>,
+}
+
+impl ArgRule {
+    fn plain(syscall: i32) -> Self {
+        ArgRule {
+            syscall,
+            args: Vec::new(),
+        }
+    }
+}
+
+fn cmp(arg: u32, op: u32, datum_a: u64, datum_b: u64) -> scmp_arg_cmp {
+    scmp_arg_cmp {
+        arg,
+        op,
+        datum_a,
+        datum_b,
+    }
+}
+
+/*
+ * resolve_syscall_name - resolve a syscall name to its native-arch number
+ * @name: syscall name, e.g. "read" or "clone"
+ *
+ * Numbers are architecture- (and compat-ABI-) specific, so names are the
+ * only thing that's portable in configuration; this is the single place
+ * that turns one back into a number for seccomp_rule_add().
+ *
+ * Deliberately resolves against the native arch only, not per extra
+ * architecture via seccomp_syscall_resolve_name_rewrite(): libseccomp
+ * itself re-translates every rule added through seccomp_rule_add() /
+ * seccomp_rule_add_array() to each architecture seccomp_arch_add() has
+ * programmed into the context (see new_filter_ctx()), silently skipping
+ * a syscall on an arch that lacks it. Resolving per arch by hand here
+ * would just duplicate that translation. warn_if_missing_on_arch() uses
+ * the rewrite form purely to surface the skip as a warning, since
+ * resolve_table() otherwise has no visibility into it.
+ *
+ * Return: the syscall number, or None if libseccomp doesn't recognize it
+ */
+pub(crate) fn resolve_syscall_name(name: &str) -> Option<i32> {
+    let cname = CString::new(name).ok()?;
+    let nr = unsafe { seccomp_syscall_resolve_name(cname.as_ptr()) };
+    if nr == __NR_SCMP_ERROR {
+        None
+    } else {
+        Some(nr)
+    }
+}
+
+/*
+ * extra_architectures - additional SCMP_ARCH_* to program beyond the native one
+ *
+ * Without these, a process can dodge a 64-bit-only filter by issuing the
+ * same call through its 32-bit compat ABI (e.g. `int 0x80` on x86_64).
+ */
+#[cfg(target_arch = "x86_64")]
+fn extra_architectures() -> &'static [u32] {
+    &[SCMP_ARCH_X86]
+}
+#[cfg(target_arch = "aarch64")]
+fn extra_architectures() -> &'static [u32] {
+    &[SCMP_ARCH_ARM]
+}
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn extra_architectures() -> &'static [u32] {
+    &[]
+}
+
+/*
+ * warn_if_missing_on_arch - log (without failing) when @name has no
+ * equivalent syscall on @arch
+ *
+ * Some syscalls genuinely don't exist on every ABI we add (e.g. 32-bit
+ * compat lacks some 64-bit-only calls); seccomp_syscall_resolve_name_rewrite()
+ * is the arch-aware counterpart of resolve_syscall_name() used to check this.
+ */
+fn warn_if_missing_on_arch(name: &str, arch: u32) {
+    let Ok(cname) = CString::new(name) else {
+        return;
+    };
+    let nr = unsafe { seccomp_syscall_resolve_name_rewrite(arch, cname.as_ptr()) };
+    if nr == __NR_SCMP_ERROR {
+        eprintln!("seccomp: syscall \"{name}\" has no equivalent on arch {arch:#x}, allowing only where it exists");
+    }
+}
+
+/*
+ * arg_rules_for - expand a bare syscall number into its ArgRule(s)
+ * @syscall: the syscall number to expand
+ *
+ * Most syscalls are allowed unconditionally, but a handful are
+ * dangerous enough with arbitrary arguments that we pin them down:
+ *   - mmap/mprotect: never allow PROT_EXEC mappings (W^X, blocks JIT'd
+ *     shellcode from becoming executable).
+ *   - socket: restrict the address family to
+ *     AF_INET/AF_INET6/AF_UNIX/AF_NETLINK.
+ *   - fcntl: restrict the command to the handful of flag, fd-duplication
+ *     and locking operations we actually need.
+ *   - clone: only match when CLONE_NEWUSER is clear; a clone() that
+ *     requests CLONE_NEWUSER (namespace escape) matches no rule for the
+ *     syscall and falls through to the context's default action instead
+ *     of this syscall's usual one.
+ *
+ * Returns one or more ArgRule entries that, taken together, are
+ * equivalent to the original bare-syscall rule but restricted. Each
+ * restricted entry is *narrower* than "allow/errno unconditionally" -- a
+ * call whose arguments match none of its rules (a PROT_EXEC mapping, an
+ * unlisted family or command, a NEWUSER clone) is deliberately left
+ * unmatched.
+ */
+fn arg_rules_for(syscall: i32) -> Vec<ArgRule> {
+    if syscall == libc::SYS_mmap as i32 || syscall == libc::SYS_mprotect as i32 {
+        return vec![ArgRule {
+            syscall,
+            args: vec![cmp(2, SCMP_CMP_MASKED_EQ, libc::PROT_EXEC as u64, 0)],
+        }];
+    }
+
+    if syscall == libc::SYS_socket as i32 {
+        /* AF_NETLINK is needed by glibc's getaddrinfo()/getifaddrs() for
+         * ordinary name resolution; without it, a single hostname lookup
+         * would hit the KILL_PROCESS default instead of falling back. */
+        return [
+            libc::AF_INET,
+            libc::AF_INET6,
+            libc::AF_UNIX,
+            libc::AF_NETLINK,
+        ]
+        .iter()
+        .map(|&af| ArgRule {
+            syscall,
+            args: vec![cmp(0, SCMP_CMP_EQ, af as u64, 0)],
+        })
+        .collect();
+    }
+
+    if syscall == libc::SYS_fcntl as i32 {
+        /* F_DUPFD/F_DUPFD_CLOEXEC/F_GETLK/F_SETLK are routine fd-duplication
+         * and locking commands libuv/Node and CPython use; omitting them
+         * would hit the KILL_PROCESS default on ordinary interpreter use. */
+        return [
+            libc::F_GETFL,
+            libc::F_SETFL,
+            libc::F_GETFD,
+            libc::F_SETFD,
+            libc::F_DUPFD,
+            libc::F_DUPFD_CLOEXEC,
+            libc::F_GETLK,
+            libc::F_SETLK,
+        ]
+        .iter()
+        .map(|&fcntl_cmd| ArgRule {
+            syscall,
+            args: vec![cmp(1, SCMP_CMP_EQ, fcntl_cmd as u64, 0)],
+        })
+        .collect();
+    }
+
+    if syscall == libc::SYS_clone as i32 {
+        /* match only when CLONE_NEWUSER is clear; a CLONE_NEWUSER clone
+         * matches no rule for this syscall and falls through to the
+         * context's default action. */
+        return vec![ArgRule {
+            syscall,
+            args: vec![cmp(0, SCMP_CMP_MASKED_EQ, libc::CLONE_NEWUSER as u64, 0)],
+        }];
+    }
+
+    vec![ArgRule::plain(syscall)]
+}
+
+/*
+ * resolve_table - resolve a table of syscall names to their ArgRule(s)
+ * @names: symbolic syscall names, e.g. ALLOW_SYSCALLS
+ *
+ * Unresolvable names (shouldn't happen for the built-in tables, but
+ * possible if libseccomp's supported syscall list drifts) are skipped
+ * rather than failing the whole sandbox setup.
+ */
+fn resolve_table(names: &[&str]) -> Vec<ArgRule> {
+    names
+        .iter()
+        .filter_map(|&name| {
+            for &arch in extra_architectures() {
+                warn_if_missing_on_arch(name, arch);
+            }
+            resolve_syscall_name(name).map(arg_rules_for)
+        })
+        .flatten()
+        .collect()
+}
+
 /*
  * get_allowed_syscalls - retrieve allowed syscalls for the sandbox
  * @enable_network: enable network-related syscalls if non-zero
  *
  * Syscall selection order:
- *   1. ALLOWED_SYSCALLS environment variable
- *   2. Built-in default allowlist
+ *   1. ALLOWED_SYSCALLS environment variable (raw numbers, native arch only)
+ *   2. Built-in default allowlist (syscall names, resolved by libseccomp)
  *   3. Optional network syscall extension
  *
+ * Every syscall is expanded via arg_rules_for() so that
+ * mmap/mprotect/socket/fcntl/clone get their argument restrictions
+ * applied regardless of which source added them to the list.
+ *
  * Returns:
  *   (allowed_syscalls, allowed_not_kill_syscalls)
  *     allowed_syscalls: syscalls fully allowed
  *     allowed_not_kill_syscalls: syscalls returning EPERM
  */
-pub fn get_allowed_syscalls(enable_network: bool) -> (Vec<i32>, Vec<i32>) {
+pub fn get_allowed_syscalls(enable_network: bool) -> (Vec<ArgRule>, Vec<ArgRule>) {
     let mut allowed_syscalls = Vec::new();
-    let mut allowed_not_kill_syscalls = Vec::new();
 
     /* Syscalls that return error instead of killing */
-    allowed_not_kill_syscalls.extend(ALLOW_ERROR_SYSCALLS);
+    let allowed_not_kill_syscalls = resolve_table(ALLOW_ERROR_SYSCALLS);
 
     /* Load from environment variable ALLOWED_SYSCALLS */
     if let Ok(env_val) = env::var("ALLOWED_SYSCALLS") {
         if !env_val.is_empty() {
             for s in env_val.split(',') {
                 if let Ok(sc) = i32::from_str(s) {
-                    allowed_syscalls.push(sc);
+                    allowed_syscalls.extend(arg_rules_for(sc));
                 }
             }
         }
@@ -54,9 +269,9 @@ pub fn get_allowed_syscalls(enable_network: bool) -> (Vec<i32>, Vec<i32>) {
 
     /* Fallback to default syscalls if env not set */
     if allowed_syscalls.is_empty() {
-        allowed_syscalls.extend(ALLOW_SYSCALLS);
+        allowed_syscalls.extend(resolve_table(ALLOW_SYSCALLS));
         if enable_network {
-            allowed_syscalls.extend(ALLOW_NETWORK_SYSCALLS);
+            allowed_syscalls.extend(resolve_table(ALLOW_NETWORK_SYSCALLS));
         }
     }
 
@@ -64,15 +279,18 @@ pub fn get_allowed_syscalls(enable_network: bool) -> (Vec<i32>, Vec<i32>) {
 }
 
 /*
- * setup_root - setup restricted filesystem root
+ * setup_root_chroot - setup restricted filesystem root via chroot
  *
- * Perform chroot(".") and change working directory to "/".
+ * Perform chroot(".") and change working directory to "/". Shares the
+ * host mount table, so a process that retains a file descriptor to a
+ * directory outside the new root can escape it; setup_root_pivot() is
+ * the stronger alternative.
  *
  * Return:
  *   0 on success
  *   negative error code on failure
  */
-fn setup_root() -> Result<(), c_int> {
+fn setup_root_chroot() -> Result<(), c_int> {
     let root = CString::new(".").unwrap();
     if unsafe { chroot(root.as_ptr()) } != 0 {
         return Err(-1);
@@ -86,6 +304,94 @@ fn setup_root() -> Result<(), c_int> {
     Ok(())
 }
 
+/*
+ * setup_root_pivot - setup restricted filesystem root via pivot_root
+ *
+ * Assumes the caller has already unshared the mount namespace
+ * (CLONE_NEWNS). Remounts "/" MS_PRIVATE so changes don't propagate back
+ * to the host, bind-mounts the current directory onto itself so it's a
+ * valid pivot_root() target, pivots into it, then detaches and removes
+ * the old root -- the approach youki and nix's pivot_root wrapper use for
+ * container roots. Unlike chroot(), this fully severs the old mount
+ * table, so a leaked fd to an outside directory no longer grants escape.
+ *
+ * Return:
+ *   0 on success
+ *   negative error code on failure
+ */
+fn setup_root_pivot() -> Result<(), c_int> {
+    unsafe {
+        let slash = CString::new("/").unwrap();
+        if libc::mount(
+            std::ptr::null(),
+            slash.as_ptr(),
+            std::ptr::null(),
+            (libc::MS_PRIVATE | libc::MS_REC) as libc::c_ulong,
+            std::ptr::null(),
+        ) != 0
+        {
+            return Err(-12);
+        }
+
+        let new_root = CString::new(".").unwrap();
+        if libc::mount(
+            new_root.as_ptr(),
+            new_root.as_ptr(),
+            std::ptr::null(),
+            libc::MS_BIND as libc::c_ulong,
+            std::ptr::null(),
+        ) != 0
+        {
+            return Err(-13);
+        }
+
+        let put_old = CString::new(".pivot_root_old").unwrap();
+        let _ = libc::mkdir(put_old.as_ptr(), 0o700);
+
+        if libc::syscall(libc::SYS_pivot_root, new_root.as_ptr(), put_old.as_ptr()) != 0 {
+            return Err(-14);
+        }
+
+        let root_dir = CString::new("/").unwrap();
+        if chdir(root_dir.as_ptr()) != 0 {
+            return Err(-15);
+        }
+
+        let put_old_abs = CString::new("/.pivot_root_old").unwrap();
+        if libc::umount2(put_old_abs.as_ptr(), libc::MNT_DETACH) != 0 {
+            return Err(-16);
+        }
+        if libc::rmdir(put_old_abs.as_ptr()) != 0 {
+            return Err(-17);
+        }
+    }
+
+    Ok(())
+}
+
+/*
+ * setup_root - setup restricted filesystem root
+ * @use_namespaces: attempt the pivot_root jail if non-zero
+ *
+ * When @use_namespaces is set, unshares a private mount namespace and
+ * uses setup_root_pivot(); callers that can't unshare (e.g. missing
+ * CAP_SYS_ADMIN, or already inside a restricted namespace) transparently
+ * fall back to the existing chroot() jail. unshare/pivot_root/umount2/
+ * mount are only ever invoked here, before install_seccomp() loads the
+ * filter -- they are deliberately not on the runtime allowlist.
+ *
+ * Return:
+ *   0 on success
+ *   negative error code on failure
+ */
+fn setup_root(use_namespaces: bool) -> Result<(), c_int> {
+    if use_namespaces && unsafe { libc::unshare(libc::CLONE_NEWNS) } == 0 {
+        return setup_root_pivot();
+    }
+
+    setup_root_chroot()
+}
+
 /*
  * set_no_new_privs - enable PR_SET_NO_NEW_PRIVS
  *
@@ -123,37 +429,213 @@ fn drop_privileges(uid: uid_t, gid: gid_t) -> Result<(), c_int> {
     Ok(())
 }
 
+/*
+ * add_rule - add a single ArgRule to a seccomp context under the given action
+ * @ctx: seccomp context
+ * @action: SCMP_ACT_* to apply when the rule matches
+ * @rule: syscall plus optional argument comparators
+ *
+ * Rules with no argument comparators fall back to seccomp_rule_add()
+ * (match any arguments); rules with comparators use
+ * seccomp_rule_add_array() so the comparators are ANDed together.
+ *
+ * Some syscalls (e.g. socket/fcntl, which some 32-bit ABIs multiplex
+ * through socketcall()) can't carry argument comparators on every
+ * architecture programmed into the context, and seccomp_rule_add_array()
+ * reports that with -EINVAL. Rather than letting one such syscall fail
+ * the entire sandbox install, fall back to an unfiltered rule for it.
+ *
+ * Return: libseccomp's return code (0 on success)
+ */
+unsafe fn add_rule(ctx: scmp_filter_ctx, action: u32, rule: &ArgRule) -> c_int {
+    if rule.args.is_empty() {
+        return unsafe { seccomp_rule_add(ctx, action, rule.syscall, 0) };
+    }
+
+    let ret = unsafe {
+        seccomp_rule_add_array(
+            ctx,
+            action,
+            rule.syscall,
+            rule.args.len() as u32,
+            rule.args.as_ptr(),
+        )
+    };
+    if ret == -libc::EINVAL {
+        eprintln!(
+            "seccomp: syscall {} can't be argument-filtered on this architecture set, allowing it unfiltered instead",
+            rule.syscall
+        );
+        return unsafe { seccomp_rule_add(ctx, action, rule.syscall, 0) };
+    }
+    ret
+}
+
+/*
+ * new_filter_ctx - seccomp_init() plus the extra architectures we cover
+ * @default_action: SCMP_ACT_* applied when no rule matches
+ *
+ * Programming only the native architecture leaves the classic seccomp
+ * bypass open: a process issues the same syscall through another ABI
+ * (e.g. the 32-bit compat ABI on x86_64) to dodge a 64-bit-only filter.
+ *
+ * Return: the new context, or Err(-6) on failure
+ */
+unsafe fn new_filter_ctx(default_action: u32) -> Result<scmp_filter_ctx, c_int> {
+    unsafe {
+        let ctx = seccomp_init(default_action);
+        if ctx.is_null() {
+            return Err(-6); /* failed to init seccomp context */
+        }
+
+        for &arch in extra_architectures() {
+            if seccomp_arch_add(ctx, arch) != 0 {
+                seccomp_release(ctx);
+                return Err(-6);
+            }
+        }
+
+        Ok(ctx)
+    }
+}
+
+/*
+ * SigsysFields - the kernel's `_sigsys` member of siginfo_t's `_sifields`
+ * union: `{ void *_call_addr; int _syscall; unsigned int _arch; }`,
+ * starting right after `si_code` (plus the padding 64-bit platforms
+ * insert to align the pointer that follows it).
+ *
+ * libc::siginfo_t doesn't expose this as an accessor on every release, so
+ * si_syscall() below reads it at its kernel-defined byte offset instead
+ * of depending on one being present.
+ */
+#[repr(C)]
+struct SigsysFields {
+    _call_addr: *mut libc::c_void,
+    syscall: c_int,
+    _arch: u32,
+}
+
+/* si_signo, si_errno, si_code: 3 x c_int, then padding to align the
+ * pointer-sized union that follows on 64-bit platforms. */
+const SIGSYS_FIELDS_OFFSET: usize = if cfg!(target_pointer_width = "64") {
+    16
+} else {
+    12
+};
+
+unsafe fn si_syscall(info: *const libc::siginfo_t) -> c_int {
+    unsafe {
+        let fields = (info as *const u8).add(SIGSYS_FIELDS_OFFSET) as *const SigsysFields;
+        (*fields).syscall
+    }
+}
+
+/*
+ * sigsys_handler - SIGSYS handler installed for SECCOMP_MODE=trap
+ *
+ * Reads the offending syscall number out of siginfo_t (populated by the
+ * kernel from the SCMP_ACT_TRAP-triggering seccomp filter) and reports it
+ * before aborting, giving a precise "blocked syscall N" diagnostic instead
+ * of a silent kill.
+ */
+extern "C" fn sigsys_handler(
+    _signum: c_int,
+    info: *mut libc::siginfo_t,
+    _ucontext: *mut libc::c_void,
+) {
+    let nr = unsafe { si_syscall(info) };
+    eprintln!("seccomp: blocked syscall {nr}");
+    unsafe { libc::abort() }
+}
+
+/*
+ * install_sigsys_handler - register sigsys_handler() for SIGSYS
+ *
+ * Uses rt_sigaction, which is already on the built-in allowlist, so this
+ * is safe to call even after the filter is loaded.
+ *
+ * Return:
+ *   0 on success
+ *   negative error code on failure
+ */
+fn install_sigsys_handler() -> Result<(), c_int> {
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = sigsys_handler as usize;
+        action.sa_flags = libc::SA_SIGINFO;
+        libc::sigemptyset(&mut action.sa_mask);
+        if libc::sigaction(libc::SIGSYS, &action, std::ptr::null_mut()) != 0 {
+            return Err(-10);
+        }
+    }
+    Ok(())
+}
+
+/*
+ * default_action_for_mode - pick the filter's default action from SECCOMP_MODE
+ *
+ * SECCOMP_MODE selects how a syscall that isn't on the allowlist is handled:
+ *   - kill (default): SCMP_ACT_KILL_PROCESS, the hardened production setting.
+ *   - log: SCMP_ACT_LOG, so denied calls are recorded to the kernel audit log
+ *     while the workload keeps running -- lets operators harvest the list of
+ *     syscalls a real workload needs.
+ *   - trap: SCMP_ACT_TRAP plus a SIGSYS handler that prints the precise
+ *     syscall number that was blocked before aborting.
+ *
+ * Return:
+ *   the SCMP_ACT_* to pass to new_filter_ctx()
+ *   negative error code if installing the trap handler fails
+ */
+fn default_action_for_mode() -> Result<u32, c_int> {
+    match env::var("SECCOMP_MODE").as_deref() {
+        Ok("log") => Ok(SCMP_ACT_LOG),
+        Ok("trap") => {
+            install_sigsys_handler()?;
+            Ok(SCMP_ACT_TRAP)
+        }
+        _ => Ok(SCMP_ACT_KILL_PROCESS),
+    }
+}
+
 /*
  * install_seccomp - install seccomp filter
  * @enable_network: enable network-related syscalls if non-zero
  *
- * Default action is SCMP_ACT_KILL_PROCESS.
- * Allowed syscalls are explicitly whitelisted.
+ * If the SECCOMP_PROFILE environment variable points at an OCI/youki-style
+ * JSON profile, it takes precedence over the built-in allowlist: its rules
+ * and its `defaultAction` are loaded verbatim, and SECCOMP_MODE is ignored
+ * since the profile already states its own default action explicitly.
+ * Otherwise the default action comes from SECCOMP_MODE (see
+ * default_action_for_mode()), falling back to get_allowed_syscalls().
  *
  * Return:
  *   0 on success
  *   negative error code on failure
  */
 fn install_seccomp(enable_network: bool) -> Result<(), c_int> {
+    if let Ok(profile_path) = env::var("SECCOMP_PROFILE") {
+        return install_seccomp_from_profile(&profile_path);
+    }
+
+    let default_action = default_action_for_mode()?;
+
     unsafe {
-        let ctx = seccomp_init(SCMP_ACT_KILL_PROCESS);
-        if ctx.is_null() {
-            return Err(-6); /* failed to init seccomp context */
-        }
+        let ctx = new_filter_ctx(default_action)?;
 
         let (allowed_syscalls, allowed_not_kill_syscalls) = get_allowed_syscalls(enable_network);
 
         /* add fully allowed syscalls */
-        for &sc in &allowed_syscalls {
-            if seccomp_rule_add(ctx, SCMP_ACT_ALLOW, sc, 0) != 0 {
+        for rule in &allowed_syscalls {
+            if add_rule(ctx, SCMP_ACT_ALLOW, rule) != 0 {
                 seccomp_release(ctx);
                 return Err(-7);
             }
         }
 
         /* add syscalls returning EPERM */
-        for &sc in &allowed_not_kill_syscalls {
-            if seccomp_rule_add(ctx, SCMP_ACT_ERRNO(libc::EPERM as u16), sc, 0) != 0 {
+        for rule in &allowed_not_kill_syscalls {
+            if add_rule(ctx, SCMP_ACT_ERRNO(libc::EPERM as u16), rule) != 0 {
                 seccomp_release(ctx);
                 return Err(-8);
             }
@@ -169,11 +651,55 @@ fn install_seccomp(enable_network: bool) -> Result<(), c_int> {
     }
 }
 
+/*
+ * install_seccomp_from_profile - install a seccomp filter from a JSON profile
+ * @profile_path: path to an OCI/youki-style JSON seccomp profile
+ *
+ * Return:
+ *   0 on success
+ *   negative error code from profile::load() or libseccomp on failure
+ */
+fn install_seccomp_from_profile(profile_path: &str) -> Result<(), c_int> {
+    let loaded = profile::load(profile_path)?;
+
+    unsafe {
+        let ctx = new_filter_ctx(loaded.default_action)?;
+
+        /* Add any architectures the profile asks for beyond the
+         * compile-time defaults; -EEXIST (already added, e.g. native or
+         * one of new_filter_ctx()'s own extras) is not an error here. */
+        for &arch in &loaded.architectures {
+            let ret = seccomp_arch_add(ctx, arch);
+            if ret != 0 && ret != -libc::EEXIST {
+                seccomp_release(ctx);
+                return Err(-6);
+            }
+        }
+
+        for resolved in &loaded.rules {
+            if add_rule(ctx, resolved.action, &resolved.rule) != 0 {
+                seccomp_release(ctx);
+                return Err(-7);
+            }
+        }
+
+        if seccomp_load(ctx) != 0 {
+            seccomp_release(ctx);
+            return Err(-9);
+        }
+
+        seccomp_release(ctx);
+        Ok(())
+    }
+}
+
 /*
  * init_seccomp - initialize seccomp sandbox
  * @uid: target user ID
  * @gid: target group ID
  * @enable_network: enable network syscalls if non-zero
+ * @use_namespaces: use the unshare()+pivot_root() jail instead of chroot()
+ *   if non-zero (falls back to chroot() if the namespace can't be created)
  *
  * Initialize the sandbox and apply privilege restrictions
  * in the following order:
@@ -190,8 +716,13 @@ fn install_seccomp(enable_network: bool) -> Result<(), c_int> {
  *   negative error code on failure
  */
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn init_seccomp(uid: uid_t, gid: gid_t, enable_network: i32) -> c_int {
-    if let Err(code) = setup_root() {
+pub unsafe extern "C" fn init_seccomp(
+    uid: uid_t,
+    gid: gid_t,
+    enable_network: i32,
+    use_namespaces: i32,
+) -> c_int {
+    if let Err(code) = setup_root(use_namespaces != 0) {
         return code;
     }
     if let Err(code) = set_no_new_privs() {
@@ -0,0 +1,309 @@
+// src/profile.rs
+//
+// Loader for OCI runtime-spec-style JSON seccomp profiles (the shape
+// youki consumes), selected via the SECCOMP_PROFILE environment
+// variable. Syscalls are identified by name rather than raw number so
+// the same profile file is portable across architectures; numbers are
+// resolved at load time via seccomp_syscall_resolve_name(). A
+// SCMP_ACT_ERRNO action returns the entry's `errnoRet`, falling back to
+// the profile's `defaultErrnoRet`, defaulting to EPERM if neither is set.
+
+use crate::ArgRule;
+use libc::c_int;
+use libseccomp_sys::*;
+use serde::Deserialize;
+use std::fs;
+
+#[derive(Deserialize)]
+pub struct Profile {
+    #[serde(rename = "defaultAction")]
+    pub default_action: String,
+    #[serde(default, rename = "defaultErrnoRet")]
+    pub default_errno_ret: Option<i32>,
+    #[serde(default)]
+    pub architectures: Vec<String>,
+    pub syscalls: Vec<SyscallEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct SyscallEntry {
+    pub names: Vec<String>,
+    pub action: String,
+    #[serde(default, rename = "errnoRet")]
+    pub errno_ret: Option<i32>,
+    #[serde(default)]
+    pub args: Vec<ArgFilter>,
+}
+
+#[derive(Deserialize)]
+pub struct ArgFilter {
+    pub index: u32,
+    pub value: u64,
+    #[serde(default, rename = "valueTwo")]
+    pub value_two: u64,
+    pub op: String,
+}
+
+/* A single resolved syscall rule: the ArgRule libseccomp needs, plus the
+ * action this entry (rather than the profile's defaultAction) requests. */
+pub struct ResolvedRule {
+    pub rule: ArgRule,
+    pub action: u32,
+}
+
+pub struct LoadedProfile {
+    pub default_action: u32,
+    pub architectures: Vec<u32>,
+    pub rules: Vec<ResolvedRule>,
+}
+
+fn arch_from_str(s: &str) -> Option<u32> {
+    Some(match s {
+        "SCMP_ARCH_NATIVE" => SCMP_ARCH_NATIVE,
+        "SCMP_ARCH_X86" => SCMP_ARCH_X86,
+        "SCMP_ARCH_X86_64" => SCMP_ARCH_X86_64,
+        "SCMP_ARCH_X32" => SCMP_ARCH_X32,
+        "SCMP_ARCH_ARM" => SCMP_ARCH_ARM,
+        "SCMP_ARCH_AARCH64" => SCMP_ARCH_AARCH64,
+        _ => return None,
+    })
+}
+
+/*
+ * action_from_str - resolve an OCI action name to an SCMP_ACT_* value
+ * @s: action name, e.g. "SCMP_ACT_ERRNO"
+ * @errno_ret: errno to use for SCMP_ACT_ERRNO, from the entry's `errnoRet`
+ *   (falling back to the profile's `defaultErrnoRet`); defaults to EPERM
+ *   to match runc/youki when the OCI spec omits the field entirely.
+ */
+fn action_from_str(s: &str, errno_ret: Option<i32>) -> Option<u32> {
+    Some(match s {
+        "SCMP_ACT_KILL_PROCESS" => SCMP_ACT_KILL_PROCESS,
+        "SCMP_ACT_KILL" | "SCMP_ACT_KILL_THREAD" => SCMP_ACT_KILL_THREAD,
+        "SCMP_ACT_TRAP" => SCMP_ACT_TRAP,
+        "SCMP_ACT_ERRNO" => SCMP_ACT_ERRNO(errno_ret.unwrap_or(libc::EPERM) as u16),
+        "SCMP_ACT_LOG" => SCMP_ACT_LOG,
+        "SCMP_ACT_ALLOW" => SCMP_ACT_ALLOW,
+        _ => return None,
+    })
+}
+
+fn op_from_str(s: &str) -> Option<u32> {
+    Some(match s {
+        "SCMP_CMP_NE" => SCMP_CMP_NE,
+        "SCMP_CMP_LT" => SCMP_CMP_LT,
+        "SCMP_CMP_LE" => SCMP_CMP_LE,
+        "SCMP_CMP_EQ" => SCMP_CMP_EQ,
+        "SCMP_CMP_GE" => SCMP_CMP_GE,
+        "SCMP_CMP_GT" => SCMP_CMP_GT,
+        "SCMP_CMP_MASKED_EQ" => SCMP_CMP_MASKED_EQ,
+        _ => return None,
+    })
+}
+
+/*
+ * load - read and parse a profile document at @path
+ * @path: filesystem path to the JSON profile, e.g. from SECCOMP_PROFILE
+ *
+ * Every `syscalls[].names` entry is resolved to a number up front so
+ * callers never have to deal with raw numbers in configuration.
+ *
+ * Return:
+ *   Ok(LoadedProfile) on success
+ *   negative error code on I/O error, malformed JSON, or an unknown
+ *   action/op/syscall/architecture name
+ */
+pub fn load(path: &str) -> Result<LoadedProfile, c_int> {
+    let text = fs::read_to_string(path).map_err(|_| -20)?;
+    let profile: Profile = serde_json::from_str(&text).map_err(|_| -21)?;
+
+    let default_action =
+        action_from_str(&profile.default_action, profile.default_errno_ret).ok_or(-22)?;
+
+    let mut architectures = Vec::new();
+    for arch in &profile.architectures {
+        architectures.push(arch_from_str(arch).ok_or(-25)?);
+    }
+
+    let mut rules = Vec::new();
+    for entry in &profile.syscalls {
+        let errno_ret = entry.errno_ret.or(profile.default_errno_ret);
+        let action = action_from_str(&entry.action, errno_ret).ok_or(-22)?;
+
+        let mut args = Vec::new();
+        for filter in &entry.args {
+            let op = op_from_str(&filter.op).ok_or(-23)?;
+            args.push(scmp_arg_cmp {
+                arg: filter.index,
+                op,
+                datum_a: filter.value,
+                datum_b: filter.value_two,
+            });
+        }
+
+        for name in &entry.names {
+            let syscall = crate::resolve_syscall_name(name).ok_or(-24)?;
+            rules.push(ResolvedRule {
+                rule: ArgRule {
+                    syscall,
+                    args: args.clone(),
+                },
+                action,
+            });
+        }
+    }
+
+    Ok(LoadedProfile {
+        default_action,
+        architectures,
+        rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /* load() only takes a path, so tests round-trip through a scratch file
+     * rather than feeding JSON to load() directly; each test gets its own
+     * path so tests can run concurrently without clobbering one another. */
+    fn write_temp(contents: &str) -> String {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "seccomp_redbear_profile_test_{}_{n}.json",
+            std::process::id()
+        ));
+        fs::write(&path, contents).expect("write temp profile");
+        path.to_str().expect("utf8 temp path").to_string()
+    }
+
+    #[test]
+    fn load_valid_profile() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ERRNO",
+                "defaultErrnoRet": 13,
+                "architectures": ["SCMP_ARCH_X86"],
+                "syscalls": [
+                    { "names": ["read", "write"], "action": "SCMP_ACT_ALLOW" },
+                    {
+                        "names": ["mprotect"],
+                        "action": "SCMP_ACT_ERRNO",
+                        "errnoRet": 1,
+                        "args": [
+                            { "index": 2, "value": 4, "op": "SCMP_CMP_MASKED_EQ" }
+                        ]
+                    }
+                ]
+            }"#,
+        );
+
+        let loaded = load(&path).expect("valid profile should load");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.default_action, SCMP_ACT_ERRNO(13));
+        assert_eq!(loaded.architectures, vec![SCMP_ARCH_X86]);
+        assert_eq!(loaded.rules.len(), 3);
+        assert_eq!(loaded.rules[2].action, SCMP_ACT_ERRNO(1));
+        assert_eq!(loaded.rules[2].rule.args.len(), 1);
+    }
+
+    #[test]
+    fn load_default_errno_ret_falls_back_to_eperm() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ERRNO",
+                "syscalls": [
+                    { "names": ["read"], "action": "SCMP_ACT_ERRNO" }
+                ]
+            }"#,
+        );
+
+        let loaded = load(&path).expect("valid profile should load");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.default_action, SCMP_ACT_ERRNO(libc::EPERM as u16));
+        assert_eq!(loaded.rules[0].action, SCMP_ACT_ERRNO(libc::EPERM as u16));
+    }
+
+    #[test]
+    fn load_missing_file_is_minus_20() {
+        assert!(matches!(
+            load("/nonexistent/seccomp-profile.json"),
+            Err(-20)
+        ));
+    }
+
+    #[test]
+    fn load_malformed_json_is_minus_21() {
+        let path = write_temp("{ not json");
+        let result = load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(-21)));
+    }
+
+    #[test]
+    fn load_unknown_action_is_minus_22() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_NOPE",
+                "syscalls": []
+            }"#,
+        );
+        let result = load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(-22)));
+    }
+
+    #[test]
+    fn load_unknown_op_is_minus_23() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ALLOW",
+                "syscalls": [
+                    {
+                        "names": ["read"],
+                        "action": "SCMP_ACT_ALLOW",
+                        "args": [
+                            { "index": 0, "value": 1, "op": "SCMP_CMP_NOPE" }
+                        ]
+                    }
+                ]
+            }"#,
+        );
+        let result = load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(-23)));
+    }
+
+    #[test]
+    fn load_unknown_syscall_name_is_minus_24() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ALLOW",
+                "syscalls": [
+                    { "names": ["not_a_real_syscall"], "action": "SCMP_ACT_ALLOW" }
+                ]
+            }"#,
+        );
+        let result = load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(-24)));
+    }
+
+    #[test]
+    fn load_unknown_arch_is_minus_25() {
+        let path = write_temp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ALLOW",
+                "architectures": ["SCMP_ARCH_NOPE"],
+                "syscalls": []
+            }"#,
+        );
+        let result = load(&path);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(-25)));
+    }
+}
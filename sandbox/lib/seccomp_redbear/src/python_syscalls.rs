@@ -0,0 +1,85 @@
+// src/python_syscalls.rs
+//
+// Syscalls are named rather than numbered: numbers are architecture-specific
+// (and even ABI-specific on 32-bit compat), so lib.rs resolves each name at
+// load time via libseccomp, which maps each rule to the right number on
+// every architecture the filter covers.
+
+pub static ALLOW_SYSCALLS: &[&str] = &[
+    // file io
+    "read",
+    "write",
+    "openat",
+    "close",
+    "newfstatat",
+    "ioctl",
+    "lseek",
+    "getdents64",
+
+    // thread
+    "futex",
+
+    // memory
+    "mmap",
+    "brk",
+    "mprotect",
+    "munmap",
+    "rt_sigreturn",
+    "mremap",
+
+    // user / group
+    "setuid",
+    "setgid",
+    "getuid",
+
+    // process
+    "getpid",
+    "getppid",
+    "gettid",
+    "exit",
+    "exit_group",
+    "tgkill",
+    "rt_sigaction",
+    "sched_yield",
+    "set_robust_list",
+    "get_robust_list",
+    "rseq",
+
+    // time
+    "clock_gettime",
+    "gettimeofday",
+    "nanosleep",
+    "epoll_create1",
+    "epoll_ctl",
+    "clock_nanosleep",
+    "pselect6",
+    "rt_sigprocmask",
+    "sigaltstack",
+    "getrandom",
+];
+
+pub static ALLOW_ERROR_SYSCALLS: &[&str] = &["clone", "mkdirat", "mkdir"];
+
+pub static ALLOW_NETWORK_SYSCALLS: &[&str] = &[
+    "socket",
+    "connect",
+    "bind",
+    "listen",
+    "accept",
+    "sendto",
+    "recvfrom",
+    "getsockname",
+    "recvmsg",
+    "getpeername",
+    "setsockopt",
+    "ppoll",
+    "uname",
+    "sendmsg",
+    "sendmmsg",
+    "getsockopt",
+    "fstat",
+    "fcntl",
+    "fstatfs",
+    "poll",
+    "epoll_pwait",
+];